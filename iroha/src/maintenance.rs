@@ -1,36 +1,124 @@
 //! `Maintenance` module provides structures and implementation blocks related to `Iroha`
 //! maintenance functions like Healthcheck, Monitoring, etc.
 
+use std::sync::Arc;
+use std::time::Duration;
+
 use iroha_derive::Io;
-use iroha_error::Result;
+use iroha_error::{Result, WrapErr};
 use parity_scale_codec::{Decode, Encode};
+use prometheus_client::encoding::text::encode;
+use prometheus_client::registry::Registry;
 use serde::{Deserialize, Serialize};
 
 use crate::config::Configuration;
 
+pub use worker::{Worker, WorkerState, WorkerStatus};
+
+/// Default interval on which the built-in `MetricsWorker` re-runs `Metrics::calculate`.
+const DEFAULT_METRICS_WORKER_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default interval on which the built-in block storage scrub is re-run.
+const DEFAULT_SCRUB_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
 /// Entry point and main entity in `maintenance` API.
 /// Provides all information about the system needed for administrators and users.
-#[derive(Debug)]
 pub struct System {
     configuration: Configuration,
+    metrics: Arc<Metrics>,
+    scrub: Arc<scrub::Scrub>,
+    identity: identity::Identity,
+    registry: Registry,
+    workers: worker::WorkerRegistry,
 }
 
 impl System {
     /// Default `System` constructor.
     pub fn new(configuration: &Configuration) -> Self {
+        let metrics = Arc::new(Metrics::new(configuration));
+        let scrub = Arc::new(scrub::Scrub::new(&configuration.kura_configuration));
+        let identity = identity::Identity::new();
+        let mut registry = Registry::default();
+        let sub_registry = registry.sub_registry_with_prefix("iroha");
+        metrics.register(sub_registry);
+        scrub.register(sub_registry);
+        identity.register(sub_registry);
         System {
             configuration: configuration.clone(),
+            metrics,
+            scrub,
+            identity,
+            registry,
+            workers: worker::WorkerRegistry::new(),
         }
     }
 
-    /// Scrape current system metrics.
+    /// Returns a wire-encodable snapshot of the most recently cached system metrics, as kept up
+    /// to date by the background `MetricsWorker` spawned from `start_workers`. This no longer
+    /// performs the cpu/disk/memory sampling itself (that would duplicate the worker's job and
+    /// race its writes to the same gauges), so it's cheap enough to call on every request; call
+    /// `start_workers` once during node start-up so the cache is actually kept fresh.
     ///
     /// # Errors
+    /// Does not currently fail; kept as a `Result` for forward compatibility.
+    pub async fn scrape_metrics(&self) -> Result<MetricsSnapshot> {
+        self.identity.calculate();
+        Ok(self.metrics.snapshot())
+    }
+
+    /// Encode the currently collected metrics in the Prometheus text exposition format, so that
+    /// they can be scraped directly by a monitoring system such as Prometheus or Grafana Agent.
     ///
-    pub async fn scrape_metrics(&self) -> Result<Metrics> {
-        let mut metrics = Metrics::new(&self.configuration);
-        metrics.calculate().await?;
-        Ok(metrics)
+    /// # Errors
+    /// Fails if the underlying registry can't be encoded.
+    pub fn encode_metrics(&self) -> Result<String> {
+        self.identity.calculate();
+        let mut buffer = vec![];
+        encode(&mut buffer, &self.registry).wrap_err("Failed to encode metrics")?;
+        String::from_utf8(buffer).wrap_err("Metrics encoding produced invalid UTF-8")
+    }
+
+    /// Spawns the built-in background workers (periodic metrics scrape and block storage scrub)
+    /// and starts running them on the tokio runtime. Should be called once, from within the
+    /// async runtime, during node start-up.
+    pub async fn start_workers(&self) {
+        let metrics_worker = Arc::new(worker::MetricsWorker::new(Arc::clone(&self.metrics)));
+        self.workers
+            .spawn(metrics_worker, DEFAULT_METRICS_WORKER_INTERVAL)
+            .await;
+        self.workers
+            .spawn(Arc::clone(&self.scrub) as Arc<dyn Worker>, DEFAULT_SCRUB_INTERVAL)
+            .await;
+    }
+
+    /// Returns the current status of every background worker, for operator introspection.
+    pub async fn list_workers(&self) -> Vec<WorkerStatus> {
+        self.workers.list().await
+    }
+
+    /// Pauses the named background worker until [`System::resume_worker`] is called.
+    pub async fn pause_worker(&self, name: &str) {
+        self.workers.pause(name).await;
+    }
+
+    /// Resumes a previously paused background worker.
+    pub async fn resume_worker(&self, name: &str) {
+        self.workers.resume(name).await;
+    }
+
+    /// Cancels the named background worker; it will not run again.
+    pub async fn cancel_worker(&self, name: &str) {
+        self.workers.cancel(name).await;
+    }
+}
+
+impl std::fmt::Debug for System {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("System")
+            .field("configuration", &self.configuration)
+            .field("metrics", &self.metrics)
+            .field("identity", &self.identity)
+            .finish()
     }
 }
 
@@ -44,9 +132,9 @@ pub enum Health {
     Ready,
 }
 
-/// Metrics struct compose all Iroha metrics and provides an ability to export them in monitoring
-/// systems.
-#[derive(Clone, Debug, Default, Io, Encode, Decode)]
+/// Metrics struct composes all Iroha metrics as typed Prometheus gauges, so that they can be
+/// registered in a `Registry` and exported in the standard exposition format.
+#[derive(Clone, Debug, Default)]
 pub struct Metrics {
     cpu: cpu::Cpu,
     disk: disk::Disk,
@@ -62,31 +150,78 @@ impl Metrics {
         }
     }
 
+    /// Register every gauge owned by this `Metrics` instance into the given `Registry`.
+    pub fn register(&self, registry: &mut Registry) {
+        self.cpu.register(registry);
+        self.disk.register(registry);
+        self.memory.register(registry);
+    }
+
     /// Update current `Metrics` state with new data.
     ///
     /// # Errors
     /// Can fail during cpu and memory usage calculations
-    pub async fn calculate(&mut self) -> Result<()> {
+    pub async fn calculate(&self) -> Result<()> {
         self.disk.calculate().await?;
         self.cpu.calculate().await?;
         self.memory.calculate().await?;
         Ok(())
     }
+
+    /// A plain-data, wire-encodable snapshot of the current gauge values, for clients that still
+    /// consume metrics as a typed SCALE-encoded response rather than scraping the Prometheus
+    /// text endpoint.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            block_storage_bytes: self.disk.block_storage_bytes(),
+            disk_available_bytes: self.disk.available_bytes(),
+            disk_total_bytes: self.disk.total_bytes(),
+            cpu_usage_ratio: self.cpu.usage_ratio(),
+            memory_used_bytes: self.memory.used_bytes(),
+            memory_swap_bytes: self.memory.swap_bytes(),
+        }
+    }
+}
+
+/// Plain-data projection of [`Metrics`] that derives `Io`/`Encode`/`Decode`, so it can be sent
+/// as a typed SCALE-encoded query/response payload. Its field layout differs from the
+/// pre-Prometheus `Metrics` struct, so it does not decode that struct's encoding.
+#[derive(Clone, Copy, Debug, Default, Io, Encode, Decode)]
+pub struct MetricsSnapshot {
+    /// See `disk::Disk::block_storage_bytes`.
+    pub block_storage_bytes: i64,
+    /// See `disk::Disk::available_bytes`.
+    pub disk_available_bytes: i64,
+    /// See `disk::Disk::total_bytes`.
+    pub disk_total_bytes: i64,
+    /// See `cpu::Load::usage_ratio`.
+    pub cpu_usage_ratio: f64,
+    /// See `memory::Memory::used_bytes`.
+    pub memory_used_bytes: i64,
+    /// See `memory::Memory::swap_bytes`.
+    pub memory_swap_bytes: i64,
 }
 
 mod disk {
-    use iroha_derive::Io;
+    use std::sync::atomic::AtomicI64;
+
     use iroha_error::{Result, WrapErr};
-    use parity_scale_codec::{Decode, Encode};
+    use prometheus_client::metrics::gauge::Gauge;
+    use prometheus_client::registry::Registry;
+    use systemstat::{Platform, System as StatSystem};
     use tokio::fs::read_dir;
     use tokio_stream::{wrappers::ReadDirStream, StreamExt};
 
     use crate::kura::config::KuraConfiguration;
 
-    #[derive(Clone, Debug, Default, Io, Encode, Decode)]
+    #[derive(Clone, Debug, Default)]
     pub struct Disk {
-        block_storage_size: u64,
         block_storage_path: String,
+        block_storage_bytes: Gauge<i64, AtomicI64>,
+        /// Free space left on the filesystem backing `block_storage_path`.
+        available_bytes: Gauge<i64, AtomicI64>,
+        /// Total capacity of the filesystem backing `block_storage_path`.
+        total_bytes: Gauge<i64, AtomicI64>,
     }
 
     impl Disk {
@@ -97,12 +232,42 @@ mod disk {
             }
         }
 
-        pub async fn calculate(&mut self) -> Result<()> {
-            let mut total_size: u64 = 0;
+        pub fn block_storage_bytes(&self) -> i64 {
+            self.block_storage_bytes.get()
+        }
+
+        pub fn available_bytes(&self) -> i64 {
+            self.available_bytes.get()
+        }
+
+        pub fn total_bytes(&self) -> i64 {
+            self.total_bytes.get()
+        }
+
+        pub fn register(&self, registry: &mut Registry) {
+            registry.register(
+                "block_storage_bytes",
+                "Total size of the block storage directory, in bytes",
+                self.block_storage_bytes.clone(),
+            );
+            registry.register(
+                "disk_available_bytes",
+                "Free space on the filesystem backing the block storage directory, in bytes",
+                self.available_bytes.clone(),
+            );
+            registry.register(
+                "disk_total_bytes",
+                "Total capacity of the filesystem backing the block storage directory, in bytes",
+                self.total_bytes.clone(),
+            );
+        }
+
+        pub async fn calculate(&self) -> Result<()> {
+            let mut total_size: i64 = 0;
             let mut stream = ReadDirStream::new(
                 read_dir(&self.block_storage_path)
                     .await
-                    .wrap_err("Failed to read block storage directoru")?,
+                    .wrap_err("Failed to read block storage directory")?,
             );
             while let Some(entry) = stream.next().await {
                 let path = entry.wrap_err("Failed to retrieve entry path")?.path();
@@ -110,22 +275,57 @@ mod disk {
                     total_size += path
                         .metadata()
                         .wrap_err("Failed to get file metadata")?
-                        .len();
+                        .len() as i64;
                 }
             }
-            self.block_storage_size = total_size;
+            self.block_storage_bytes.set(total_size);
+            self.calculate_free_space().await?;
+            Ok(())
+        }
+
+        /// Queries the total and available capacity of the filesystem backing
+        /// `block_storage_path`. `systemstat` performs blocking syscalls internally, so the call
+        /// is moved onto a blocking thread.
+        async fn calculate_free_space(&self) -> Result<()> {
+            let path = self.block_storage_path.clone();
+            #[allow(clippy::cast_possible_wrap)]
+            let (available_bytes, total_bytes) =
+                tokio::task::spawn_blocking(move || -> Result<(i64, i64)> {
+                    let filesystem = StatSystem::new()
+                        .mount_at(&path)
+                        .wrap_err("Failed to stat filesystem backing block storage")?;
+                    Ok((
+                        filesystem.avail.as_u64() as i64,
+                        filesystem.total.as_u64() as i64,
+                    ))
+                })
+                .await
+                .wrap_err("Failed to join filesystem stat task")??;
+            self.available_bytes.set(available_bytes);
+            self.total_bytes.set(total_bytes);
             Ok(())
         }
     }
 }
 
 mod cpu {
+    use std::sync::atomic::AtomicU64;
+    use std::time::Duration;
+
+    use futures::TryStreamExt;
     use heim::cpu;
-    use iroha_derive::Io;
+    use heim::units::time::second;
     use iroha_error::Result;
-    use parity_scale_codec::{Decode, Encode};
+    use prometheus_client::encoding::text::Encode;
+    use prometheus_client::metrics::family::Family;
+    use prometheus_client::metrics::gauge::Gauge;
+    use prometheus_client::registry::Registry;
+
+    /// Delay between the two `cpu::time` samples used to compute utilization. Short enough to
+    /// feel live, long enough that the two samples aren't dominated by measurement noise.
+    const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_millis(200);
 
-    #[derive(Clone, Debug, Default, Io, Encode, Decode)]
+    #[derive(Clone, Debug, Default)]
     pub struct Cpu {
         load: Load,
     }
@@ -135,17 +335,41 @@ mod cpu {
             Cpu::default()
         }
 
+        pub fn usage_ratio(&self) -> f64 {
+            self.load.usage_ratio()
+        }
+
+        pub fn register(&self, registry: &mut Registry) {
+            self.load.register(registry);
+        }
+
         #[iroha_futures::telemetry_future]
-        pub async fn calculate(&mut self) -> Result<()> {
+        pub async fn calculate(&self) -> Result<()> {
             self.load.calculate().await
         }
     }
 
-    #[derive(Clone, Debug, Default, Io, Encode, Decode)]
+    /// Label identifying a single logical core in the `cpu_core_usage_ratio` metric family.
+    #[derive(Clone, Debug, Hash, PartialEq, Eq, Encode)]
+    struct CoreLabel {
+        core: u64,
+    }
+
+    #[derive(Clone, Debug)]
     pub struct Load {
-        frequency: String,
-        stats: String,
-        time: String,
+        sample_interval: Duration,
+        usage_ratio: Gauge<f64, AtomicU64>,
+        per_core_usage_ratio: Family<CoreLabel, Gauge<f64, AtomicU64>>,
+    }
+
+    impl Default for Load {
+        fn default() -> Self {
+            Load {
+                sample_interval: DEFAULT_SAMPLE_INTERVAL,
+                usage_ratio: Gauge::default(),
+                per_core_usage_ratio: Family::default(),
+            }
+        }
     }
 
     impl Load {
@@ -153,30 +377,149 @@ mod cpu {
             Load::default()
         }
 
-        /// Calculates cpu usage
+        pub fn usage_ratio(&self) -> f64 {
+            self.usage_ratio.get()
+        }
+
+        /// Creates a `Load` that samples CPU time `interval` apart instead of the default.
+        pub fn with_interval(interval: Duration) -> Self {
+            Load {
+                sample_interval: interval,
+                ..Load::default()
+            }
+        }
+
+        pub fn register(&self, registry: &mut Registry) {
+            registry.register(
+                "cpu_usage_ratio",
+                "Fraction of CPU time spent busy over the last sampling interval, in the [0, 1] range",
+                self.usage_ratio.clone(),
+            );
+            registry.register(
+                "cpu_core_usage_ratio",
+                "Per logical core fraction of CPU time spent busy over the last sampling interval",
+                self.per_core_usage_ratio.clone(),
+            );
+        }
+
+        /// Calculates cpu usage by taking two `heim::cpu::time`/`heim::cpu::times` samples
+        /// `self.sample_interval` apart and comparing the busy and total time elapsed between
+        /// them.
         ///
         /// # Errors
         /// Can fail during computing metrics
         #[iroha_futures::telemetry_future]
-        pub async fn calculate(&mut self) -> Result<()> {
-            self.frequency = format!("{:?}", cpu::frequency().await);
-            self.stats = format!("{:?}", cpu::stats().await);
-            self.time = format!("{:?}", cpu::time().await);
+        pub async fn calculate(&self) -> Result<()> {
+            let before = cpu::time().await?;
+            let before_per_core: Vec<_> = cpu::times().try_collect().await?;
+            tokio::time::sleep(self.sample_interval).await;
+            let after = cpu::time().await?;
+            let after_per_core: Vec<_> = cpu::times().try_collect().await?;
+
+            self.usage_ratio.set(usage_ratio(
+                busy_seconds(&before),
+                busy_seconds(&after),
+                before.idle().get::<second>(),
+                after.idle().get::<second>(),
+            ));
+
+            for (core, (before, after)) in before_per_core.iter().zip(after_per_core.iter()).enumerate() {
+                #[allow(clippy::cast_possible_truncation)]
+                let label = CoreLabel { core: core as u64 };
+                self.per_core_usage_ratio.get_or_create(&label).set(usage_ratio(
+                    busy_seconds(before),
+                    busy_seconds(after),
+                    before.idle().get::<second>(),
+                    after.idle().get::<second>(),
+                ));
+            }
             Ok(())
         }
     }
+
+    /// Seconds of "busy" CPU time reported by a single `CpuTime` sample.
+    ///
+    /// `user`/`system` are the only categories heim guarantees across every platform it
+    /// supports. `nice`/`irq`/`softirq`/`steal` are Linux-specific accounting categories,
+    /// exposed through `heim::cpu::os::linux::CpuTimeExt`, and are folded in on that platform;
+    /// elsewhere they don't exist so there's nothing to add.
+    #[cfg(target_os = "linux")]
+    fn busy_seconds(time: &cpu::CpuTime) -> f64 {
+        use heim::cpu::os::linux::CpuTimeExt;
+        (time.user() + time.system() + time.nice() + time.irq() + time.softirq() + time.steal())
+            .get::<second>()
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn busy_seconds(time: &cpu::CpuTime) -> f64 {
+        (time.user() + time.system()).get::<second>()
+    }
+
+    /// Computes a busy/total ratio from the busy/idle seconds of two samples, clamped to
+    /// `[0, 1]`.
+    ///
+    /// Returns `0.0` if the total delta is non-positive, which can happen if the counters appear
+    /// to go backwards (e.g. after a host suspend/resume cycle) or if the sampling interval was
+    /// too short to observe any elapsed time.
+    fn usage_ratio(busy_before: f64, busy_after: f64, idle_before: f64, idle_after: f64) -> f64 {
+        let total_before = busy_before + idle_before;
+        let total_after = busy_after + idle_after;
+
+        let busy_delta = busy_after - busy_before;
+        let total_delta = total_after - total_before;
+
+        if total_delta <= 0.0 {
+            0.0
+        } else {
+            (busy_delta / total_delta).clamp(0.0, 1.0)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        #![allow(clippy::restriction)]
+
+        use super::*;
+
+        #[test]
+        fn zero_delta_yields_zero() {
+            assert_eq!(usage_ratio(10.0, 10.0, 5.0, 5.0), 0.0);
+        }
+
+        #[test]
+        fn backwards_counters_yield_zero() {
+            // Counters appearing to go backwards (e.g. after a reset) must not produce a
+            // negative or otherwise nonsensical ratio.
+            assert_eq!(usage_ratio(10.0, 4.0, 5.0, 1.0), 0.0);
+        }
+
+        #[test]
+        fn ratio_is_clamped_to_one() {
+            // Idle time appearing to go backwards makes the raw ratio 100 / 90, above 1.
+            assert_eq!(usage_ratio(0.0, 100.0, 10.0, 0.0), 1.0);
+        }
+
+        #[test]
+        fn typical_sample_computes_expected_ratio() {
+            // 2 seconds busy, 8 seconds idle elapsed between samples -> 20% utilization.
+            assert!((usage_ratio(0.0, 2.0, 0.0, 8.0) - 0.2).abs() < f64::EPSILON);
+        }
+    }
 }
 
 mod memory {
+    use std::sync::atomic::AtomicI64;
+
     use heim::memory;
-    use iroha_derive::Io;
+    use heim::units::information::byte;
     use iroha_error::Result;
-    use parity_scale_codec::{Decode, Encode};
+    use prometheus_client::metrics::gauge::Gauge;
+    use prometheus_client::registry::Registry;
 
-    #[derive(Clone, Debug, Default, Io, Encode, Decode)]
+    #[derive(Clone, Debug, Default)]
     pub struct Memory {
-        memory: String,
-        swap: String,
+        used_bytes: Gauge<i64, AtomicI64>,
+        swap_bytes: Gauge<i64, AtomicI64>,
     }
 
     impl Memory {
@@ -184,14 +527,42 @@ mod memory {
             Memory::default()
         }
 
+        pub fn used_bytes(&self) -> i64 {
+            self.used_bytes.get()
+        }
+
+        pub fn swap_bytes(&self) -> i64 {
+            self.swap_bytes.get()
+        }
+
+        pub fn register(&self, registry: &mut Registry) {
+            registry.register(
+                "memory_used_bytes",
+                "Currently used RAM, in bytes",
+                self.used_bytes.clone(),
+            );
+            registry.register(
+                "memory_swap_bytes",
+                "Currently used swap space, in bytes",
+                self.swap_bytes.clone(),
+            );
+        }
+
         /// Calculates memory usage
         ///
         /// # Errors
         /// Can fail during computing memory metrics
         #[iroha_futures::telemetry_future]
-        pub async fn calculate(&mut self) -> Result<()> {
-            self.memory = format!("{:?}", memory::memory().await);
-            self.swap = format!("{:?}", memory::swap().await);
+        pub async fn calculate(&self) -> Result<()> {
+            let mem = memory::memory().await?;
+            #[allow(clippy::cast_possible_wrap)]
+            let used = (mem.total() - mem.available()).get::<byte>() as i64;
+            self.used_bytes.set(used);
+
+            let swap = memory::swap().await?;
+            #[allow(clippy::cast_possible_wrap)]
+            let swap_used = swap.used().get::<byte>() as i64;
+            self.swap_bytes.set(swap_used);
             Ok(())
         }
     }
@@ -204,13 +575,504 @@ mod memory {
 
         #[tokio::test]
         async fn test_calculate_memory() {
-            let mut memory = Memory::default();
+            let memory = Memory::default();
             memory
                 .calculate()
                 .await
                 .expect("Failed to calculate memory.");
-            assert!(!memory.memory.is_empty());
-            assert!(!memory.swap.is_empty());
+            // A running test process always has some resident memory, so this can only pass if
+            // `calculate` actually populated the gauge instead of leaving its `0` default.
+            assert!(memory.used_bytes.get() > 0);
         }
     }
-}
\ No newline at end of file
+}
+
+mod worker {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use iroha_error::Result;
+    use tokio::sync::{mpsc, RwLock};
+
+    use super::Metrics;
+
+    /// A background maintenance job that `WorkerRegistry` can run repeatedly on a timer.
+    #[async_trait]
+    pub trait Worker: Send + Sync + 'static {
+        /// Human readable name, used to identify this worker in a [`WorkerStatus`] and to target
+        /// it through [`super::System::pause_worker`] and friends.
+        fn name(&self) -> &str;
+
+        /// Performs a single unit of work.
+        ///
+        /// # Errors
+        /// Implementations should return an error instead of panicking; the registry records it
+        /// in [`WorkerStatus::last_error`] and keeps the worker alive for the next iteration.
+        async fn step(&self) -> Result<()>;
+    }
+
+    /// Current lifecycle state of a background worker.
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum WorkerState {
+        /// The worker is currently executing `step`.
+        Active,
+        /// The worker is waiting for its next scheduled iteration, or has been paused.
+        Idle,
+        /// The worker's task has exited and will not run again.
+        Dead,
+    }
+
+    /// A point-in-time snapshot of a worker's health, returned by `System::list_workers`.
+    #[derive(Clone, Debug)]
+    pub struct WorkerStatus {
+        /// The worker's name, as returned by [`Worker::name`].
+        pub name: String,
+        /// The worker's current lifecycle state.
+        pub state: WorkerState,
+        /// How many times `step` has completed, successfully or not.
+        pub iterations: u64,
+        /// The error returned by the most recent `step`, if any.
+        pub last_error: Option<String>,
+    }
+
+    /// Messages accepted by a worker's control channel.
+    enum Control {
+        Pause,
+        Resume,
+        Cancel,
+    }
+
+    struct Handle {
+        name: String,
+        control: mpsc::UnboundedSender<Control>,
+        status: Arc<RwLock<WorkerStatus>>,
+    }
+
+    /// Owns every background worker spawned by `System`, and exposes introspection and control
+    /// over them.
+    #[derive(Default)]
+    pub struct WorkerRegistry {
+        handles: RwLock<HashMap<String, Handle>>,
+    }
+
+    impl WorkerRegistry {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Spawns `worker` on a new tokio task, running `step` every `interval` until paused or
+        /// cancelled.
+        pub async fn spawn(&self, worker: Arc<dyn Worker>, interval: Duration) {
+            let name = worker.name().to_owned();
+            let (control_sender, mut control_receiver) = mpsc::unbounded_channel();
+            let status = Arc::new(RwLock::new(WorkerStatus {
+                name: name.clone(),
+                state: WorkerState::Idle,
+                iterations: 0,
+                last_error: None,
+            }));
+            let task_status = Arc::clone(&status);
+            let _join_handle = tokio::spawn(async move {
+                let mut paused = false;
+                loop {
+                    tokio::select! {
+                        _ = tokio::time::sleep(interval), if !paused => {
+                            task_status.write().await.state = WorkerState::Active;
+                            let result = worker.step().await;
+                            let mut status = task_status.write().await;
+                            status.iterations += 1;
+                            status.state = WorkerState::Idle;
+                            status.last_error = result.err().map(|error| error.to_string());
+                        }
+                        message = control_receiver.recv() => {
+                            match message {
+                                Some(Control::Pause) => paused = true,
+                                Some(Control::Resume) => paused = false,
+                                Some(Control::Cancel) | None => break,
+                            }
+                        }
+                    }
+                }
+                task_status.write().await.state = WorkerState::Dead;
+            });
+            self.handles.write().await.insert(
+                name.clone(),
+                Handle {
+                    name,
+                    control: control_sender,
+                    status,
+                },
+            );
+        }
+
+        /// Returns a status snapshot for every registered worker.
+        pub async fn list(&self) -> Vec<WorkerStatus> {
+            let mut statuses = Vec::new();
+            for handle in self.handles.read().await.values() {
+                statuses.push(handle.status.read().await.clone());
+            }
+            statuses
+        }
+
+        /// Pauses the named worker, if one is registered under that name.
+        pub async fn pause(&self, name: &str) {
+            self.send(name, Control::Pause).await;
+        }
+
+        /// Resumes a previously paused worker.
+        pub async fn resume(&self, name: &str) {
+            self.send(name, Control::Resume).await;
+        }
+
+        /// Cancels the named worker; it will not run again.
+        pub async fn cancel(&self, name: &str) {
+            self.send(name, Control::Cancel).await;
+        }
+
+        async fn send(&self, name: &str, control: Control) {
+            if let Some(handle) = self.handles.read().await.get(name) {
+                // The receiving end only disappears once the task has exited, in which case
+                // there's nothing left to control.
+                let _ = handle.control.send(control);
+            }
+        }
+    }
+
+    impl std::fmt::Debug for Handle {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("Handle").field("name", &self.name).finish()
+        }
+    }
+
+    /// Built-in worker that keeps `Metrics` fresh by re-running `Metrics::calculate` on an
+    /// interval, so that readers (e.g. `System::encode_metrics`) always see a recent snapshot
+    /// without paying the calculation cost on every scrape.
+    pub struct MetricsWorker {
+        metrics: Arc<Metrics>,
+    }
+
+    impl MetricsWorker {
+        pub fn new(metrics: Arc<Metrics>) -> Self {
+            MetricsWorker { metrics }
+        }
+    }
+
+    #[async_trait]
+    impl Worker for MetricsWorker {
+        fn name(&self) -> &str {
+            "metrics"
+        }
+
+        async fn step(&self) -> Result<()> {
+            self.metrics.calculate().await
+        }
+    }
+}
+
+mod scrub {
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::AtomicI64;
+    use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+    use async_trait::async_trait;
+    use iroha_error::{Result, WrapErr};
+    use parity_scale_codec::Decode;
+    use prometheus_client::metrics::gauge::Gauge;
+    use prometheus_client::registry::Registry;
+    use serde::{Deserialize, Serialize};
+    use tokio::fs::{read_dir, read_to_string, write};
+    use tokio::sync::RwLock;
+    use tokio_stream::{wrappers::ReadDirStream, StreamExt};
+
+    use iroha_crypto::MerkleTree;
+
+    use super::worker::Worker;
+    use crate::block::VersionedCommittedBlock;
+    use crate::kura::config::KuraConfiguration;
+
+    /// Default throttle: after processing a block, sleep for as long as that block took to
+    /// process, halving the scrub's I/O share against the rest of the peer.
+    const DEFAULT_TRANQUILITY: f64 = 1.0;
+
+    const STATE_FILE_NAME: &str = ".scrub_state.json";
+
+    /// How many verified blocks to batch between persisted checkpoints of `ScrubState`.
+    const STATE_SAVE_INTERVAL_BLOCKS: usize = 50;
+
+    /// On-disk record of scrub progress, so that a restart resumes an interrupted pass instead
+    /// of re-walking it from the start.
+    #[derive(Clone, Debug, Default, Serialize, Deserialize)]
+    struct ScrubState {
+        /// Height of the highest block verified so far in the pass currently underway. Reset to
+        /// `0` once a pass completes, so the *next scheduled* run starts a fresh full pass
+        /// rather than only ever looking at newly appended blocks — otherwise bit rot in
+        /// already-scrubbed blocks would never be found again.
+        last_completed_height: u64,
+        /// Corrupt blocks found so far in the pass currently underway, persisted alongside
+        /// `last_completed_height` so that a restart mid-pass doesn't lose a previously detected
+        /// corruption signal. Reset to `0` only when a new pass begins (i.e. alongside
+        /// `last_completed_height` going back to `0`), never when merely resuming one.
+        corrupt_blocks_found: i64,
+        last_scrub_unix_ms: u128,
+    }
+
+    /// Periodically walks the block storage directory, re-reading and decoding each block to
+    /// detect silent corruption, without starving the peer's own disk I/O.
+    pub struct Scrub {
+        block_storage_path: PathBuf,
+        state_path: PathBuf,
+        tranquility: f64,
+        corrupt_blocks: Gauge<i64, AtomicI64>,
+        state: RwLock<ScrubState>,
+    }
+
+    impl Scrub {
+        pub fn new(configuration: &KuraConfiguration) -> Self {
+            let block_storage_path = PathBuf::from(&configuration.kura_block_store_path);
+            let state_path = block_storage_path.join(STATE_FILE_NAME);
+            Scrub {
+                block_storage_path,
+                state_path,
+                tranquility: DEFAULT_TRANQUILITY,
+                corrupt_blocks: Gauge::default(),
+                state: RwLock::new(ScrubState::default()),
+            }
+        }
+
+        /// Overrides the default tranquility (how gently the scrub throttles itself between
+        /// blocks).
+        #[must_use]
+        pub fn with_tranquility(mut self, tranquility: f64) -> Self {
+            self.tranquility = tranquility;
+            self
+        }
+
+        pub fn register(&self, registry: &mut Registry) {
+            registry.register(
+                "scrub_corrupt_blocks",
+                "Number of block files found corrupt or unreadable by the most recent scrub",
+                self.corrupt_blocks.clone(),
+            );
+        }
+
+        async fn load_state(&self) -> ScrubState {
+            match read_to_string(&self.state_path).await {
+                Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
+                Err(_) => ScrubState::default(),
+            }
+        }
+
+        async fn save_state(&self, state: &ScrubState) -> Result<()> {
+            let contents =
+                serde_json::to_string(state).wrap_err("Failed to serialize scrub state")?;
+            write(&self.state_path, contents)
+                .await
+                .wrap_err("Failed to persist scrub state")
+        }
+
+        /// Walks every block file not yet covered by the pass currently underway, verifying
+        /// that each one decodes, reports its own height, and has a merkle root matching its
+        /// header, throttling between files according to `tranquility`. Once every block has
+        /// been visited, resets the watermark so the next
+        /// scheduled run starts a fresh pass and keeps re-verifying the whole store, rather than
+        /// only ever looking at newly appended blocks.
+        async fn scrub_once(&self) -> Result<()> {
+            let mut state = self.state.read().await.clone();
+            if state.last_scrub_unix_ms == 0 {
+                state = self.load_state().await;
+            }
+            if state.last_completed_height == 0 {
+                // Starting a fresh pass (either the very first one, or the one after a previous
+                // pass completed): the gauge keeps showing the previous pass's total until this
+                // one starts producing fresh counts.
+                state.corrupt_blocks_found = 0;
+            }
+
+            let mut entries = Vec::new();
+            let mut stream = ReadDirStream::new(
+                read_dir(&self.block_storage_path)
+                    .await
+                    .wrap_err("Failed to read block storage directory")?,
+            );
+            while let Some(entry) = stream.next().await {
+                let path = entry.wrap_err("Failed to retrieve entry path")?.path();
+                if !path.is_file() || path == self.state_path {
+                    continue;
+                }
+                if let Some(height) = block_height(&path) {
+                    entries.push((height, path));
+                }
+            }
+            // `read_dir` makes no ordering guarantee, but the resume watermark below only makes
+            // sense against a height-ordered walk.
+            entries.sort_unstable_by_key(|(height, _)| *height);
+
+            let mut blocks_since_checkpoint = 0_usize;
+            for (height, path) in &entries {
+                if *height <= state.last_completed_height {
+                    continue;
+                }
+
+                let started = Instant::now();
+                if !verify_block(path, *height).await {
+                    state.corrupt_blocks_found += 1;
+                }
+                state.last_completed_height = *height;
+                self.corrupt_blocks.set(state.corrupt_blocks_found);
+
+                // Persisting progress after every single block would turn the scrub into a
+                // steady stream of small synchronous writes, defeating the whole point of the
+                // tranquility throttle below. Checkpoint in batches instead, trading a bounded
+                // amount of re-verification after a crash for far less I/O.
+                blocks_since_checkpoint += 1;
+                if blocks_since_checkpoint >= STATE_SAVE_INTERVAL_BLOCKS {
+                    self.save_state(&state).await?;
+                    blocks_since_checkpoint = 0;
+                }
+
+                if self.tranquility > 0.0 {
+                    tokio::time::sleep(started.elapsed().mul_f64(self.tranquility)).await;
+                }
+            }
+
+            state.last_completed_height = 0;
+            state.last_scrub_unix_ms = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis())
+                .unwrap_or_default();
+            self.save_state(&state).await?;
+            *self.state.write().await = state;
+            Ok(())
+        }
+    }
+
+    /// Parses the block height out of a block file's name (e.g. `42.block`).
+    fn block_height(path: &Path) -> Option<u64> {
+        path.file_stem()?.to_str()?.parse().ok()
+    }
+
+    /// Reads and decodes the block at `path`, verifying that it is actually intact rather than
+    /// just well-formed: the block must report `expected_height` as its own height, *and* the
+    /// merkle root recomputed from its transactions must match the `transactions_hash` the
+    /// block's own header claims. A corrupted transaction payload changes its hash, which
+    /// changes the recomputed merkle root, so this catches bit rot in the transaction bodies
+    /// even though the bytes still happily SCALE-decode. An unexpected (future) block version is
+    /// treated as "not verified" rather than unwrapped, since a version this scrub doesn't know
+    /// about isn't evidence of corruption.
+    async fn verify_block(path: &Path, expected_height: u64) -> bool {
+        let bytes = match tokio::fs::read(path).await {
+            Ok(bytes) => bytes,
+            Err(_) => return false,
+        };
+        let block = match VersionedCommittedBlock::decode(&mut bytes.as_slice()) {
+            Ok(VersionedCommittedBlock::V1(block)) => block,
+            Ok(_) | Err(_) => return false,
+        };
+        if block.header.height != expected_height {
+            return false;
+        }
+        let recomputed_transactions_hash =
+            MerkleTree::new(block.transactions.iter().map(|transaction| transaction.hash()))
+                .root_hash();
+        recomputed_transactions_hash == block.header.transactions_hash
+    }
+
+    #[async_trait]
+    impl Worker for Scrub {
+        fn name(&self) -> &str {
+            "block_scrub"
+        }
+
+        async fn step(&self) -> Result<()> {
+            self.scrub_once().await
+        }
+    }
+}
+
+mod identity {
+    use std::sync::atomic::AtomicU64;
+    use std::time::{Instant, SystemTime};
+
+    use prometheus_client::encoding::text::Encode;
+    use prometheus_client::metrics::family::Family;
+    use prometheus_client::metrics::gauge::Gauge;
+    use prometheus_client::registry::Registry;
+    use uuid::Uuid;
+
+    /// Git commit this binary was built from, embedded at compile time.
+    const GIT_VERSION: &str = git_version::git_version!(fallback = "unknown");
+
+    /// Static process identity, exposed as a Prometheus "info" metric (a gauge pinned at `1`,
+    /// labelled with the values of interest) so that label-valued data like ids and versions can
+    /// be scraped the same way as everything else.
+    #[derive(Clone, Debug, Hash, PartialEq, Eq, Encode)]
+    struct InfoLabel {
+        instance_id: String,
+        version: String,
+        machine_id: String,
+    }
+
+    /// One-time-captured identity of this process, established when `System` is constructed.
+    ///
+    /// This lets operators tell restarts apart and correlate metrics with a specific process
+    /// lifetime even when wall clocks are unreliable, which the cpu/disk/memory-only `Metrics`
+    /// struct has no way to express.
+    #[derive(Clone, Debug)]
+    pub struct Identity {
+        instance_id: Uuid,
+        started_at: Instant,
+        started_at_unix: SystemTime,
+        uptime_seconds: Gauge<f64, AtomicU64>,
+        info: Family<InfoLabel, Gauge>,
+    }
+
+    impl Identity {
+        /// Captures a new identity for the current process: a random instance id and the
+        /// current time as start time. The build's git version and the machine id (where
+        /// available) are attached as labels when the identity is registered.
+        pub fn new() -> Self {
+            Identity {
+                instance_id: Uuid::new_v4(),
+                started_at: Instant::now(),
+                started_at_unix: SystemTime::now(),
+                uptime_seconds: Gauge::default(),
+                info: Family::default(),
+            }
+        }
+
+        /// The process's start time, in case a caller needs to correlate it with other
+        /// wall-clock timestamped data (e.g. log lines).
+        pub fn started_at_unix(&self) -> SystemTime {
+            self.started_at_unix
+        }
+
+        pub fn register(&self, registry: &mut Registry) {
+            registry.register(
+                "uptime_seconds",
+                "Seconds elapsed since this process started",
+                self.uptime_seconds.clone(),
+            );
+            registry.register(
+                "build_info",
+                "Process identity: instance id, build version/commit and machine id. Always 1.",
+                self.info.clone(),
+            );
+            let machine_id = machine_uid::get().unwrap_or_else(|_| "unknown".to_owned());
+            self.info
+                .get_or_create(&InfoLabel {
+                    instance_id: self.instance_id.to_string(),
+                    version: GIT_VERSION.to_owned(),
+                    machine_id,
+                })
+                .set(1);
+        }
+
+        /// Refreshes the `uptime_seconds` gauge from the captured start time.
+        pub fn calculate(&self) {
+            self.uptime_seconds
+                .set(self.started_at.elapsed().as_secs_f64());
+        }
+    }
+}